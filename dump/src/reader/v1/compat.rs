@@ -0,0 +1,204 @@
+use std::io::Read;
+
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use time::OffsetDateTime;
+
+use super::super::{DumpReader, IndexReader};
+use super::{settings, update, V1Reader};
+use crate::{Result, Version};
+
+/// Forward-migration layer turning a v1 dump into a reader that conforms to the
+/// latest [`DumpReader`] shape.
+///
+/// v1 predates most of the concepts the current dump format carries, so the
+/// migration re-shapes what v1 *does* expose and synthesises defaults for the
+/// rest:
+///
+/// * settings are remapped field-by-field onto their modern equivalents (the
+///   v1 `attributesForFaceting` becomes `filterableAttributes`, and the
+///   sortable-attributes concept v1 lacks is defaulted to an empty set),
+/// * the v1 [`update::UpdateStatus`] task model is translated into the current
+///   task model, mapping the states that still exist and passing the v1
+///   status name through unchanged for the ones that don't,
+/// * keys are `Infallible` (v1 has none) and update files collapse to `()`.
+pub struct CompatV1ToLatest {
+    from: V1Reader,
+}
+
+impl CompatV1ToLatest {
+    /// Wrap a freshly opened [`V1Reader`] so it can be consumed as a
+    /// latest-format dump.
+    pub fn new(from: V1Reader) -> Self {
+        CompatV1ToLatest { from }
+    }
+
+    /// Open an already-extracted v1 dump directory and migrate it in one pass.
+    pub fn open(dump: TempDir) -> Result<Self> {
+        Ok(CompatV1ToLatest::new(V1Reader::open(dump)?))
+    }
+
+    /// Open a v1 dump straight from a gzip-compressed tar archive and migrate it
+    /// in one pass, so the top-level loader can restore a raw `.dump` file on a
+    /// current Meilisearch without any manual preprocessing.
+    pub fn open_from_reader<R: Read>(reader: R) -> Result<Self> {
+        Ok(CompatV1ToLatest::new(V1Reader::open_from_reader(reader)?))
+    }
+}
+
+/// Map a v1 settings payload onto the modern settings shape.
+fn migrate_settings(settings: settings::Settings) -> Result<Value> {
+    let mut value = serde_json::to_value(settings)?;
+    if let Value::Object(map) = &mut value {
+        // v1 called filterable attributes "attributesForFaceting".
+        if let Some(faceting) = map.remove("attributesForFaceting") {
+            map.insert("filterableAttributes".to_string(), faceting);
+        }
+        // v1 has no dedicated sortable attributes; complete the modern shape
+        // with an empty set rather than leaving the key absent.
+        map.entry("sortableAttributes")
+            .or_insert_with(|| Value::Array(Vec::new()));
+    }
+    Ok(value)
+}
+
+/// Translate a v1 update record into the modern task shape, mapping every
+/// top-level field the current `Task` model expects and synthesising the
+/// concepts v1 lacks.
+fn migrate_task(task: update::UpdateStatus) -> Result<Value> {
+    let raw = serde_json::to_value(task)?;
+
+    // v1 externally tags the status; the inner object carries the update id,
+    // the operation kind, the error and the timestamps. Older exporters that
+    // flattened the record are handled by falling back to the record itself.
+    let (variant, content) = match raw.as_object() {
+        Some(map) if map.len() == 1 && map.values().next().is_some_and(Value::is_object) => {
+            let (variant, content) = map.iter().next().expect("the map holds exactly one entry");
+            (variant.as_str(), content.clone())
+        }
+        _ => (
+            raw.get("status").and_then(Value::as_str).unwrap_or("Enqueued"),
+            raw.clone(),
+        ),
+    };
+
+    let status = match variant {
+        "Enqueued" => "enqueued",
+        "Processing" => "processing",
+        "Processed" => "succeeded",
+        "Aborted" => "canceled",
+        "Failed" => "failed",
+        other => other,
+    };
+
+    let field = |name: &str| content.get(name).cloned().unwrap_or(Value::Null);
+    let processed_at = content
+        .get("processed_at")
+        .or_else(|| content.get("processedAt"))
+        .cloned()
+        .unwrap_or(Value::Null);
+    // A processed/failed/canceled task ran, so its v1 `processed_at` stands in
+    // for both `startedAt` and `finishedAt`; an enqueued task has neither yet.
+    let (started_at, finished_at) = match status {
+        "enqueued" => (Value::Null, Value::Null),
+        _ => (processed_at.clone(), processed_at),
+    };
+
+    Ok(json!({
+        "uid": field("update_id"),
+        // v1 merges all indexes' update logs and drops the per-task index uid.
+        "indexUid": Value::Null,
+        "status": status,
+        "type": content.get("update_type").cloned().unwrap_or_else(|| field("type")),
+        "canceledBy": Value::Null,
+        "details": field("meta"),
+        "error": field("error"),
+        "enqueuedAt": content
+            .get("enqueued_at")
+            .or_else(|| content.get("enqueuedAt"))
+            .cloned()
+            .unwrap_or(Value::Null),
+        "startedAt": started_at,
+        "finishedAt": finished_at,
+    }))
+}
+
+/// Adapts a v1 index reader so its settings come out in the modern shape.
+struct CompatIndexV1ToLatest {
+    inner: Box<dyn IndexReader<Document = Value, Settings = settings::Settings>>,
+}
+
+impl IndexReader for CompatIndexV1ToLatest {
+    type Document = Value;
+    type Settings = Value;
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn documents(&self) -> Result<Box<dyn Iterator<Item = Self::Document>>> {
+        self.inner.documents()
+    }
+
+    fn settings(&self) -> Result<Self::Settings> {
+        migrate_settings(self.inner.settings()?)
+    }
+}
+
+impl DumpReader for CompatV1ToLatest {
+    type Document = Value;
+    type Settings = Value;
+
+    type Task = Value;
+    type UpdateFile = ();
+
+    type Key = std::convert::Infallible;
+
+    fn date(&self) -> Result<Option<OffsetDateTime>> {
+        self.from.date()
+    }
+
+    fn version(&self) -> Version {
+        // The data still originates from a v1 archive even though we expose it
+        // through the latest reader shape.
+        self.from.version()
+    }
+
+    fn indexes(
+        &self,
+    ) -> Result<
+        Box<
+            dyn Iterator<
+                Item = Box<
+                    dyn IndexReader<Document = Self::Document, Settings = Self::Settings>,
+                >,
+            >,
+        >,
+    > {
+        let indexes = self.from.indexes()?;
+        Ok(Box::new(indexes.map(|inner| {
+            Box::new(CompatIndexV1ToLatest { inner })
+                as Box<dyn IndexReader<Document = Self::Document, Settings = Self::Settings>>
+        })))
+    }
+
+    fn tasks(
+        &self,
+    ) -> Result<Box<dyn Iterator<Item = Result<(Self::Task, Option<Self::UpdateFile>)>>>> {
+        let tasks = self.from.tasks()?;
+        Ok(Box::new(tasks.map(|result| {
+            let (task, update_file) = result?;
+            Ok((migrate_task(task)?, update_file))
+        })))
+    }
+
+    fn keys(&self) -> Result<Box<dyn Iterator<Item = Self::Key>>> {
+        Ok(Box::new(std::iter::empty()))
+    }
+}
+
+impl From<V1Reader> for CompatV1ToLatest {
+    fn from(from: V1Reader) -> Self {
+        CompatV1ToLatest::new(from)
+    }
+}